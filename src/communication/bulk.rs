@@ -0,0 +1,324 @@
+use super::control::{self, AbortStatus};
+use crate::constants::misc::BULK_HEADER_SIZE;
+use crate::constants::msgid;
+use crate::error::Error;
+use crate::types::{BTag, Capabilities, Handle, Timeout};
+
+use anyhow::Result;
+
+/// Largest chunk requested from the device in a single `REQUEST_DEV_DEP_MSG_IN`.
+const MAX_TRANSFER_SIZE: u32 = 1024 * 1024;
+
+/// Build the 12-byte USBTMC bulk-OUT transfer header (`DEV_DEP_MSG_OUT`,
+/// `VENDOR_SPECIFIC_OUT`, `TRIGGER`). `eom` sets the EOM attribute bit,
+/// marking the end of the message.
+fn build_header(msg_id: u8, btag: u8, transfer_size: u32, eom: bool) -> [u8; BULK_HEADER_SIZE] {
+    let attributes = if eom { 0x01 } else { 0x00 };
+    build_header_raw(msg_id, btag, transfer_size, attributes, 0)
+}
+
+/// Build the 12-byte USBTMC bulk-IN request header (`REQUEST_DEV_DEP_MSG_IN`,
+/// `REQUEST_VENDOR_SPECIFIC_IN`). That header has no EOM concept; bit 1 of
+/// its attributes byte is TermCharEnabled instead, which this sets only when
+/// `term_char` is given, so the device may end the transfer early on that byte.
+fn build_request_header(msg_id: u8, btag: u8, transfer_size: u32, term_char: Option<u8>) -> [u8; BULK_HEADER_SIZE] {
+    let mut attributes = 0x00;
+    let term_char_byte = if let Some(term_char) = term_char {
+        attributes |= 0x02;
+        term_char
+    } else {
+        0
+    };
+    build_header_raw(msg_id, btag, transfer_size, attributes, term_char_byte)
+}
+
+fn build_header_raw(msg_id: u8, btag: u8, transfer_size: u32, attributes: u8, term_char_byte: u8) -> [u8; BULK_HEADER_SIZE] {
+    let mut header = [0u8; BULK_HEADER_SIZE];
+
+    header[0] = msg_id;
+    header[1] = btag;
+    header[2] = !btag;
+    // header[3] reserved
+    header[4..8].copy_from_slice(&transfer_size.to_le_bytes());
+    header[8] = attributes;
+    header[9] = term_char_byte;
+    // header[10..12] reserved
+
+    header
+}
+
+/// Pad `packet` with zero bytes so its length is a multiple of 4, as
+/// required for every USBTMC bulk transfer.
+fn pad_to_multiple_of_4(packet: &mut Vec<u8>) {
+    while packet.len() % 4 != 0 {
+        packet.push(0);
+    }
+}
+
+/// Delay between `CHECK_ABORT_BULK_*_STATUS` polls, so recovering from a
+/// stalled transfer doesn't hammer the device with back-to-back control
+/// transfers while the abort is still pending.
+const ABORT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Run `INITIATE_ABORT_BULK_OUT` and poll `CHECK_ABORT_BULK_OUT_STATUS` until
+/// the device confirms the transfer tagged with `btag` was aborted.
+pub fn abort_bulk_out(handle: &Handle, endpoint: &u8, btag: u8, timeout: &Timeout) -> Result<()> {
+    control::initiate_abort_bulk_out(handle, *endpoint, btag, timeout)?;
+
+    while control::check_abort_bulk_out_status(handle, *endpoint, timeout)? == AbortStatus::Pending {
+        std::thread::sleep(ABORT_POLL_INTERVAL);
+    }
+
+    Ok(())
+}
+
+/// Run `INITIATE_ABORT_BULK_IN` and poll `CHECK_ABORT_BULK_IN_STATUS` until
+/// the device confirms the transfer tagged with `btag` was aborted, draining
+/// any data the device still has buffered in between polls.
+pub fn abort_bulk_in(handle: &Handle, endpoint: &u8, btag: u8, timeout: &Timeout) -> Result<()> {
+    control::initiate_abort_bulk_in(handle, *endpoint, btag, timeout)?;
+
+    let mut drain_buf = vec![0u8; BULK_HEADER_SIZE + MAX_TRANSFER_SIZE as usize];
+
+    loop {
+        match control::check_abort_bulk_in_status(handle, *endpoint, timeout)? {
+            AbortStatus::Success => break,
+            AbortStatus::Pending => {
+                let _ = handle.borrow().read_bulk(*endpoint, &mut drain_buf, *timeout.borrow());
+                std::thread::sleep(ABORT_POLL_INTERVAL);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_message(
+    handle: &Handle,
+    btag: &BTag,
+    msg_id: u8,
+    data: &[u8],
+    endpoint: &u8,
+    timeout: &Timeout,
+) -> Result<()> {
+    let tag = btag.next_out();
+    let header = build_header(msg_id, tag, data.len() as u32, true);
+
+    let mut packet = Vec::with_capacity(BULK_HEADER_SIZE + data.len());
+    packet.extend_from_slice(&header);
+    packet.extend_from_slice(data);
+    pad_to_multiple_of_4(&mut packet);
+
+    match handle.borrow().write_bulk(*endpoint, &packet, *timeout.borrow()) {
+        Ok(_) => Ok(()),
+        Err(rusb::Error::Timeout) => {
+            abort_bulk_out(handle, endpoint, tag, timeout)?;
+            Err(Error::Timeout.into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Write a DEV_DEP_MSG_OUT message to the bulk-out endpoint.
+///
+/// On timeout, the transfer is aborted with `INITIATE_ABORT_BULK_OUT` so the
+/// device and the bTag stream stay in sync for the next call.
+pub fn write(handle: &Handle, btag: &BTag, data: Vec<u8>, endpoint: &u8, timeout: &Timeout) -> Result<()> {
+    write_message(handle, btag, msgid::DEV_DEP_MSG_OUT, &data, endpoint, timeout)
+}
+
+/// Write a VENDOR_SPECIFIC_OUT message to the bulk-out endpoint, for
+/// instruments that expose a proprietary vendor channel alongside the
+/// SCPI-style device-dependent messages.
+pub fn write_vendor(handle: &Handle, btag: &BTag, data: &[u8], endpoint: &u8, timeout: &Timeout) -> Result<()> {
+    write_message(handle, btag, msgid::VENDOR_SPECIFIC_OUT, data, endpoint, timeout)
+}
+
+/// Write a USB488 TRIGGER message to the bulk-out endpoint.
+pub fn trigger(handle: &Handle, btag: &BTag, endpoint: &u8, timeout: &Timeout) -> Result<()> {
+    let tag = btag.next_out();
+    let mut packet = build_header(msgid::TRIGGER, tag, 0, true).to_vec();
+    pad_to_multiple_of_4(&mut packet);
+
+    handle.borrow().write_bulk(*endpoint, &packet, *timeout.borrow())?;
+
+    Ok(())
+}
+
+fn read_message(
+    handle: &Handle,
+    btag: &BTag,
+    bulk_in_ep: &u8,
+    bulk_out_ep: &u8,
+    request_msg_id: u8,
+    response_msg_id: u8,
+    timeout: &Timeout,
+) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+
+    loop {
+        let tag = btag.next_in();
+        let request_header = build_request_header(request_msg_id, tag, MAX_TRANSFER_SIZE, None);
+
+        match handle.borrow().write_bulk(*bulk_out_ep, &request_header, *timeout.borrow()) {
+            Ok(_) => {}
+            Err(rusb::Error::Timeout) => {
+                abort_bulk_out(handle, bulk_out_ep, tag, timeout)?;
+                return Err(Error::Timeout.into());
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        let mut buf = vec![0u8; BULK_HEADER_SIZE + MAX_TRANSFER_SIZE as usize];
+        let read_len = match handle.borrow().read_bulk(*bulk_in_ep, &mut buf, *timeout.borrow()) {
+            Ok(len) => len,
+            Err(rusb::Error::Timeout) => {
+                abort_bulk_in(handle, bulk_in_ep, tag, timeout)?;
+                return Err(Error::Timeout.into());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if read_len < BULK_HEADER_SIZE {
+            return Err(Error::InvalidResponse("bulk-in response shorter than the USBTMC header".into()).into());
+        }
+        if buf[0] != response_msg_id {
+            return Err(Error::InvalidResponse("unexpected MsgID in bulk-in response".into()).into());
+        }
+        if buf[1] != tag {
+            return Err(Error::InvalidResponse("bTag mismatch in bulk-in response".into()).into());
+        }
+
+        let transfer_size = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+        let eom = buf[8] & 0x01 != 0;
+
+        if transfer_size > MAX_TRANSFER_SIZE as usize || transfer_size > read_len - BULK_HEADER_SIZE {
+            return Err(Error::InvalidResponse("transfer_size in bulk-in header exceeds data actually read".into()).into());
+        }
+
+        data.extend_from_slice(&buf[BULK_HEADER_SIZE..BULK_HEADER_SIZE + transfer_size]);
+
+        if eom {
+            break;
+        }
+    }
+
+    Ok(data)
+}
+
+/// Request a DEV_DEP_MSG_IN response and read it from the bulk-in endpoint,
+/// issuing as many `REQUEST_DEV_DEP_MSG_IN` transfers as needed until the
+/// device sets the EOM bit.
+///
+/// On timeout, the bulk-out request or the bulk-in read (whichever stalled)
+/// is aborted via the USBTMC abort sequence, so the device and the bTag
+/// stream stay in sync for the next call.
+pub fn read(
+    handle: &Handle,
+    btag: &BTag,
+    bulk_in_ep: &u8,
+    bulk_out_ep: &u8,
+    _capabilities: &Capabilities,
+    timeout: &Timeout,
+) -> Result<Vec<u8>> {
+    read_message(
+        handle,
+        btag,
+        bulk_in_ep,
+        bulk_out_ep,
+        msgid::REQUEST_DEV_DEP_MSG_IN,
+        msgid::DEV_DEP_MSG_IN,
+        timeout,
+    )
+}
+
+/// Request a DEV_DEP_MSG_IN response and stream it to `sink` as each chunk
+/// arrives, instead of accumulating the whole transfer in memory. Honors the
+/// device's TermChar capability: if `term_char` is given and the device
+/// supports it, the device may end the transfer early on that byte.
+///
+/// On timeout, the bulk-out request or the bulk-in read (whichever stalled)
+/// is aborted via the USBTMC abort sequence, so the device and the bTag
+/// stream stay in sync for the next call.
+pub fn read_stream(
+    handle: &Handle,
+    btag: &BTag,
+    bulk_in_ep: &u8,
+    bulk_out_ep: &u8,
+    capabilities: &Capabilities,
+    term_char: Option<u8>,
+    sink: &mut impl std::io::Write,
+    timeout: &Timeout,
+) -> Result<()> {
+    let term_char = term_char.filter(|_| capabilities.term_char_enabled);
+
+    loop {
+        let tag = btag.next_in();
+        let request_header = build_request_header(msgid::REQUEST_DEV_DEP_MSG_IN, tag, MAX_TRANSFER_SIZE, term_char);
+
+        match handle.borrow().write_bulk(*bulk_out_ep, &request_header, *timeout.borrow()) {
+            Ok(_) => {}
+            Err(rusb::Error::Timeout) => {
+                abort_bulk_out(handle, bulk_out_ep, tag, timeout)?;
+                return Err(Error::Timeout.into());
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        let mut buf = vec![0u8; BULK_HEADER_SIZE + MAX_TRANSFER_SIZE as usize];
+        let read_len = match handle.borrow().read_bulk(*bulk_in_ep, &mut buf, *timeout.borrow()) {
+            Ok(len) => len,
+            Err(rusb::Error::Timeout) => {
+                abort_bulk_in(handle, bulk_in_ep, tag, timeout)?;
+                return Err(Error::Timeout.into());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if read_len < BULK_HEADER_SIZE {
+            return Err(Error::InvalidResponse("bulk-in response shorter than the USBTMC header".into()).into());
+        }
+        if buf[0] != msgid::DEV_DEP_MSG_IN {
+            return Err(Error::InvalidResponse("unexpected MsgID in bulk-in response".into()).into());
+        }
+        if buf[1] != tag {
+            return Err(Error::InvalidResponse("bTag mismatch in bulk-in response".into()).into());
+        }
+
+        let transfer_size = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+        let eom = buf[8] & 0x01 != 0;
+
+        if transfer_size > MAX_TRANSFER_SIZE as usize || transfer_size > read_len - BULK_HEADER_SIZE {
+            return Err(Error::InvalidResponse("transfer_size in bulk-in header exceeds data actually read".into()).into());
+        }
+
+        sink.write_all(&buf[BULK_HEADER_SIZE..BULK_HEADER_SIZE + transfer_size])?;
+
+        if eom {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Request a VENDOR_SPECIFIC_IN response and read it from the bulk-in
+/// endpoint, for instruments that expose a proprietary vendor channel
+/// alongside the SCPI-style device-dependent messages.
+pub fn read_vendor(
+    handle: &Handle,
+    btag: &BTag,
+    bulk_in_ep: &u8,
+    bulk_out_ep: &u8,
+    timeout: &Timeout,
+) -> Result<Vec<u8>> {
+    read_message(
+        handle,
+        btag,
+        bulk_in_ep,
+        bulk_out_ep,
+        msgid::REQUEST_VENDOR_SPECIFIC_IN,
+        msgid::VENDOR_SPECIFIC_IN,
+        timeout,
+    )
+}
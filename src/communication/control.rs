@@ -0,0 +1,280 @@
+use crate::constants::{request, status};
+use crate::error::Error;
+use crate::types::{BTag, Capabilities, Handle, Timeout};
+
+use anyhow::Result;
+use rusb::{Direction, Recipient, RequestType};
+
+/// Length in bytes of the `GET_CAPABILITIES` response.
+const CAPABILITIES_RESPONSE_LEN: usize = 0x18;
+
+fn interface_in_request() -> u8 {
+    rusb::request_type(Direction::In, RequestType::Class, Recipient::Interface)
+}
+
+/// Read the device's USBTMC/USB488 capabilities.
+pub fn get_capabilities(
+    handle: &Handle,
+    interface_number: u8,
+    timeout: &Timeout,
+) -> Result<Capabilities> {
+    let mut buf = [0u8; CAPABILITIES_RESPONSE_LEN];
+
+    handle.borrow().read_control(
+        interface_in_request(),
+        request::GET_CAPABILITIES,
+        0,
+        interface_number as u16,
+        &mut buf,
+        *timeout.borrow(),
+    )?;
+
+    if buf[0] != status::SUCCESS {
+        return Err(Error::InvalidResponse("GET_CAPABILITIES failed".into()).into());
+    }
+
+    let interface_caps = buf[4];
+    let device_caps = buf[5];
+    let usb488_interface_caps = buf[14];
+
+    // Bit assignments per the USB488 subclass spec's "USB488 interface
+    // capabilities" table: D0 is 488.2-compliant, D1 is REN_CONTROL/
+    // GO_TO_LOCAL/LOCAL_LOCKOUT support, D2 is TRIGGER support. Indicator
+    // pulse is a USBTMC (not USB488) device capability, D2 of `interface_caps`.
+    Ok(Capabilities {
+        usb488: usb488_interface_caps & 0x01 != 0,
+        supports_trigger: usb488_interface_caps & 0x04 != 0,
+        supports_remote_local: usb488_interface_caps & 0x02 != 0,
+        supports_indicator_pulse: interface_caps & 0x04 != 0,
+        term_char_enabled: device_caps & 0x01 != 0,
+    })
+}
+
+/// Run the `INITIATE_CLEAR` / `CHECK_CLEAR_STATUS` sequence to flush the
+/// device's buffers and reset its bTag state.
+pub fn clear_buffers(handle: &Handle, interface_number: u8, timeout: &Timeout) -> Result<()> {
+    let mut status_byte = [0u8; 1];
+    handle.borrow().read_control(
+        interface_in_request(),
+        request::INITIATE_CLEAR,
+        0,
+        interface_number as u16,
+        &mut status_byte,
+        *timeout.borrow(),
+    )?;
+
+    loop {
+        let mut buf = [0u8; 2];
+        handle.borrow().read_control(
+            interface_in_request(),
+            request::CHECK_CLEAR_STATUS,
+            0,
+            interface_number as u16,
+            &mut buf,
+            *timeout.borrow(),
+        )?;
+
+        if buf[0] == status::SUCCESS {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Where the status byte requested by `READ_STATUS_BYTE` ends up.
+pub enum StatusByteSource {
+    /// The device returned the status byte directly in the control response.
+    Immediate(u8),
+    /// The device has an interrupt-IN endpoint and will deliver the status
+    /// byte there, tagged with this bTag.
+    Interrupt { btag: u8 },
+}
+
+/// Issue `READ_STATUS_BYTE`.
+///
+/// Devices without an interrupt-IN endpoint return the status byte directly
+/// in the 3-byte control response (`[status, bTag, status_byte]`). Devices
+/// with an interrupt-IN endpoint instead return a 1-byte control response and
+/// deliver the status byte asynchronously over that endpoint, so callers must
+/// read it from there, matching on the returned bTag.
+pub fn read_status_byte(
+    handle: &Handle,
+    interface_number: u8,
+    btag: &BTag,
+    has_interrupt_endpoint: bool,
+    timeout: &Timeout,
+) -> Result<StatusByteSource> {
+    let tag = btag.next_control();
+
+    if has_interrupt_endpoint {
+        let mut buf = [0u8; 1];
+        handle.borrow().read_control(
+            interface_in_request(),
+            request::READ_STATUS_BYTE,
+            tag as u16,
+            interface_number as u16,
+            &mut buf,
+            *timeout.borrow(),
+        )?;
+
+        if buf[0] != status::SUCCESS {
+            return Err(Error::InvalidResponse("READ_STATUS_BYTE failed".into()).into());
+        }
+
+        return Ok(StatusByteSource::Interrupt { btag: tag });
+    }
+
+    let mut buf = [0u8; 3];
+    handle.borrow().read_control(
+        interface_in_request(),
+        request::READ_STATUS_BYTE,
+        tag as u16,
+        interface_number as u16,
+        &mut buf,
+        *timeout.borrow(),
+    )?;
+
+    if buf[0] != status::SUCCESS {
+        return Err(Error::InvalidResponse("READ_STATUS_BYTE failed".into()).into());
+    }
+    if buf[1] != tag {
+        return Err(Error::InvalidResponse("bTag mismatch in READ_STATUS_BYTE response".into()).into());
+    }
+
+    Ok(StatusByteSource::Immediate(buf[2]))
+}
+
+/// Run a USB488 control request that only returns a single USBTMC_status byte.
+fn usb488_status_request(
+    handle: &Handle,
+    interface_number: u8,
+    b_request: u8,
+    w_value: u16,
+    timeout: &Timeout,
+) -> Result<()> {
+    let mut status_byte = [0u8; 1];
+
+    handle.borrow().read_control(
+        interface_in_request(),
+        b_request,
+        w_value,
+        interface_number as u16,
+        &mut status_byte,
+        *timeout.borrow(),
+    )?;
+
+    if status_byte[0] != status::SUCCESS {
+        return Err(Error::InvalidResponse("USB488 control request failed".into()).into());
+    }
+
+    Ok(())
+}
+
+/// Enable or disable remote control via `REN_CONTROL`.
+pub fn ren_control(handle: &Handle, interface_number: u8, enable: bool, timeout: &Timeout) -> Result<()> {
+    usb488_status_request(handle, interface_number, request::REN_CONTROL, enable as u16, timeout)
+}
+
+/// Return the device to local control via `GO_TO_LOCAL`.
+pub fn go_to_local(handle: &Handle, interface_number: u8, timeout: &Timeout) -> Result<()> {
+    usb488_status_request(handle, interface_number, request::GO_TO_LOCAL, 0, timeout)
+}
+
+/// Disable the device's front-panel `LOCAL` key via `LOCAL_LOCKOUT`.
+pub fn local_lockout(handle: &Handle, interface_number: u8, timeout: &Timeout) -> Result<()> {
+    usb488_status_request(handle, interface_number, request::LOCAL_LOCKOUT, 0, timeout)
+}
+
+fn endpoint_in_request() -> u8 {
+    rusb::request_type(Direction::In, RequestType::Class, Recipient::Endpoint)
+}
+
+/// Outcome of polling `CHECK_ABORT_BULK_IN_STATUS` / `CHECK_ABORT_BULK_OUT_STATUS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbortStatus {
+    /// The device finished aborting the transfer.
+    Success,
+    /// The abort is still in progress; keep polling.
+    Pending,
+}
+
+fn initiate_abort(handle: &Handle, b_request: u8, endpoint: u8, btag: u8, timeout: &Timeout) -> Result<()> {
+    let mut buf = [0u8; 2];
+
+    handle.borrow().read_control(
+        endpoint_in_request(),
+        b_request,
+        btag as u16,
+        endpoint as u16,
+        &mut buf,
+        *timeout.borrow(),
+    )?;
+
+    if buf[0] != status::SUCCESS {
+        return Err(Error::InvalidResponse("INITIATE_ABORT failed".into()).into());
+    }
+    if buf[1] != btag {
+        return Err(Error::InvalidResponse("bTag mismatch in INITIATE_ABORT response".into()).into());
+    }
+
+    Ok(())
+}
+
+/// Start aborting the bulk-out transfer tagged with `btag`.
+pub fn initiate_abort_bulk_out(handle: &Handle, endpoint: u8, btag: u8, timeout: &Timeout) -> Result<()> {
+    initiate_abort(handle, request::INITIATE_ABORT_BULK_OUT, endpoint, btag, timeout)
+}
+
+/// Start aborting the bulk-in transfer tagged with `btag`.
+pub fn initiate_abort_bulk_in(handle: &Handle, endpoint: u8, btag: u8, timeout: &Timeout) -> Result<()> {
+    initiate_abort(handle, request::INITIATE_ABORT_BULK_IN, endpoint, btag, timeout)
+}
+
+fn check_abort_status(handle: &Handle, b_request: u8, endpoint: u8, timeout: &Timeout) -> Result<AbortStatus> {
+    let mut buf = [0u8; 8];
+
+    handle.borrow().read_control(
+        endpoint_in_request(),
+        b_request,
+        0,
+        endpoint as u16,
+        &mut buf,
+        *timeout.borrow(),
+    )?;
+
+    match buf[0] {
+        status::SUCCESS => Ok(AbortStatus::Success),
+        status::PENDING => Ok(AbortStatus::Pending),
+        _ => Err(Error::InvalidResponse("CHECK_ABORT_STATUS failed".into()).into()),
+    }
+}
+
+/// Poll the abort status of a bulk-out transfer.
+pub fn check_abort_bulk_out_status(handle: &Handle, endpoint: u8, timeout: &Timeout) -> Result<AbortStatus> {
+    check_abort_status(handle, request::CHECK_ABORT_BULK_OUT_STATUS, endpoint, timeout)
+}
+
+/// Poll the abort status of a bulk-in transfer.
+pub fn check_abort_bulk_in_status(handle: &Handle, endpoint: u8, timeout: &Timeout) -> Result<AbortStatus> {
+    check_abort_status(handle, request::CHECK_ABORT_BULK_IN_STATUS, endpoint, timeout)
+}
+
+/// Clear a halted endpoint via the standard `CLEAR_FEATURE(ENDPOINT_HALT)` request.
+pub fn clear_feature(handle: &Handle, endpoint: &u8) -> Result<()> {
+    const CLEAR_FEATURE: u8 = 0x01;
+    const ENDPOINT_HALT: u16 = 0x00;
+
+    let request_type = rusb::request_type(Direction::Out, RequestType::Standard, Recipient::Endpoint);
+
+    handle.borrow().write_control(
+        request_type,
+        CLEAR_FEATURE,
+        ENDPOINT_HALT,
+        *endpoint as u16,
+        &[],
+        std::time::Duration::from_secs(1),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::error::Error;
+use crate::types::Handle;
+
+use anyhow::Result;
+use std::time::Duration;
+
+/// A decoded 2-byte packet read from the interrupt-IN endpoint.
+#[derive(Debug, Clone, Copy)]
+pub enum Notification {
+    /// An unsolicited Service Request (`bNotify1` == 0x81), carrying the status byte.
+    ServiceRequest(u8),
+    /// The status byte requested via `READ_STATUS_BYTE`, tagged with the bTag
+    /// that was sent on the control endpoint.
+    StatusByte { btag: u8, status_byte: u8 },
+}
+
+/// Read and decode a single interrupt-IN packet.
+pub fn read_notification(handle: &Handle, endpoint: u8, timeout: Duration) -> Result<Notification> {
+    let mut buf = [0u8; 2];
+    handle.borrow().read_interrupt(endpoint, &mut buf, timeout)?;
+
+    let (notify1, notify2) = (buf[0], buf[1]);
+
+    if notify1 == 0x81 {
+        Ok(Notification::ServiceRequest(notify2))
+    } else if notify1 & 0x80 != 0 {
+        Ok(Notification::StatusByte {
+            btag: notify1 & 0x7F,
+            status_byte: notify2,
+        })
+    } else {
+        Err(Error::InvalidResponse("unrecognized interrupt-IN notification".into()).into())
+    }
+}
+
+/// Block until an unsolicited Service Request notification arrives, returning
+/// its status byte. Status-byte notifications tied to a `READ_STATUS_BYTE`
+/// request are ignored.
+pub fn wait_for_srq(handle: &Handle, endpoint: u8, timeout: Duration) -> Result<u8> {
+    loop {
+        match read_notification(handle, endpoint, timeout)? {
+            Notification::ServiceRequest(status_byte) => return Ok(status_byte),
+            Notification::StatusByte { .. } => continue,
+        }
+    }
+}
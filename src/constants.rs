@@ -0,0 +1,58 @@
+//! Protocol-level constants for USBTMC and the USB488 subclass.
+//!
+//! Values are taken from the USB Test and Measurement Class specification
+//! (USBTMC) and its USB488 subclass addendum.
+
+pub mod misc {
+    use std::time::Duration;
+
+    /// Default timeout applied to every USB transfer until overridden with
+    /// [`UsbtmcClient::set_timeout`](crate::UsbtmcClient::set_timeout).
+    pub const DEFAULT_TIMEOUT_DURATION: Duration = Duration::from_secs(5);
+
+    /// USB interface class reserved for test & measurement devices.
+    pub const USBTMC_INTERFACE_CLASS: u8 = 0xFE;
+    /// USBTMC interface subclass.
+    pub const USBTMC_INTERFACE_SUBCLASS: u8 = 0x03;
+
+    /// Size in bytes of a USBTMC bulk transfer header.
+    pub const BULK_HEADER_SIZE: usize = 12;
+}
+
+/// bRequest values accepted on the USBTMC/USB488 control endpoint.
+pub mod request {
+    // USBTMC (Table 9 of the USBTMC specification)
+    pub const INITIATE_ABORT_BULK_OUT: u8 = 1;
+    pub const CHECK_ABORT_BULK_OUT_STATUS: u8 = 2;
+    pub const INITIATE_ABORT_BULK_IN: u8 = 3;
+    pub const CHECK_ABORT_BULK_IN_STATUS: u8 = 4;
+    pub const INITIATE_CLEAR: u8 = 5;
+    pub const CHECK_CLEAR_STATUS: u8 = 6;
+    pub const GET_CAPABILITIES: u8 = 7;
+    pub const INDICATOR_PULSE: u8 = 64;
+
+    // USB488 subclass
+    pub const READ_STATUS_BYTE: u8 = 128;
+    pub const REN_CONTROL: u8 = 160;
+    pub const GO_TO_LOCAL: u8 = 161;
+    pub const LOCAL_LOCKOUT: u8 = 162;
+}
+
+/// USBTMC_status values returned by the control endpoint.
+pub mod status {
+    pub const SUCCESS: u8 = 0x01;
+    pub const PENDING: u8 = 0x02;
+    pub const FAILED: u8 = 0x80;
+}
+
+/// MsgID values used in the 12-byte bulk transfer header.
+pub mod msgid {
+    pub const DEV_DEP_MSG_OUT: u8 = 1;
+    pub const REQUEST_DEV_DEP_MSG_IN: u8 = 2;
+    pub const DEV_DEP_MSG_IN: u8 = 2;
+    pub const VENDOR_SPECIFIC_OUT: u8 = 126;
+    pub const REQUEST_VENDOR_SPECIFIC_IN: u8 = 127;
+    pub const VENDOR_SPECIFIC_IN: u8 = 127;
+    /// USB488 subclass message sent to trigger a device via the bulk-out endpoint.
+    pub const TRIGGER: u8 = 128;
+}
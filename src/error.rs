@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Errors produced by this crate, as opposed to errors bubbled up from
+/// `rusb` or other dependencies.
+#[derive(Debug)]
+pub enum Error {
+    /// No USB device matched the requested VID/PID (and, if given, serial
+    /// number) or bus/address, or the device has no USBTMC interface with
+    /// both a bulk-in and a bulk-out endpoint.
+    DeviceNotFound,
+    /// The operation requires USB488 support, but the device only advertises
+    /// the base USBTMC subclass.
+    NotUsb488,
+    /// The operation requires an interrupt-IN endpoint, but the device
+    /// doesn't expose one.
+    NoInterruptEndpoint,
+    /// The device's `GET_CAPABILITIES` response didn't advertise the
+    /// capability bit the requested operation needs.
+    CapabilityNotSupported(&'static str),
+    /// The device sent back something that doesn't match the protocol.
+    InvalidResponse(String),
+    /// A bulk transfer did not complete before the configured timeout.
+    Timeout,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::DeviceNotFound => write!(f, "no matching USBTMC device was found"),
+            Error::NotUsb488 => write!(f, "device does not advertise USB488 support"),
+            Error::NoInterruptEndpoint => write!(f, "device does not expose an interrupt-IN endpoint"),
+            Error::CapabilityNotSupported(capability) => {
+                write!(f, "device does not advertise support for {capability}")
+            }
+            Error::InvalidResponse(msg) => write!(f, "invalid response from device: {msg}"),
+            Error::Timeout => write!(f, "USB transfer timed out"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
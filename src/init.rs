@@ -0,0 +1,172 @@
+use crate::constants::misc::{USBTMC_INTERFACE_CLASS, USBTMC_INTERFACE_SUBCLASS};
+use crate::error::Error;
+use crate::types::{DeviceMode, UsbtmcDeviceInfo, UsbtmcEndpoints};
+
+use anyhow::Result;
+use rusb::{Context, Device, DeviceHandle, Direction, TransferType, UsbContext};
+
+/// Open the first device matching `vid`/`pid`, optionally restricted to one
+/// reporting the given USB serial number.
+pub fn open_device(
+    context: &mut Context,
+    vid: u16,
+    pid: u16,
+    serial: Option<&str>,
+) -> Option<(Device<Context>, DeviceHandle<Context>)> {
+    let devices = context.devices().ok()?;
+
+    for device in devices.iter() {
+        let descriptor = match device.device_descriptor() {
+            Ok(descriptor) => descriptor,
+            Err(_) => continue,
+        };
+        if descriptor.vendor_id() != vid || descriptor.product_id() != pid {
+            continue;
+        }
+
+        let handle = match device.open() {
+            Ok(handle) => handle,
+            Err(_) => continue,
+        };
+
+        if let Some(serial) = serial {
+            let reported_serial = handle.read_serial_number_string_ascii(&descriptor).ok();
+            if reported_serial.as_deref() != Some(serial) {
+                continue;
+            }
+        }
+
+        return Some((device, handle));
+    }
+
+    None
+}
+
+/// Open the device at a specific bus/address, as previously reported by
+/// [`list_devices`].
+pub fn open_device_at(
+    context: &mut Context,
+    bus_number: u8,
+    address: u8,
+) -> Option<(Device<Context>, DeviceHandle<Context>)> {
+    let devices = context.devices().ok()?;
+
+    for device in devices.iter() {
+        if device.bus_number() == bus_number && device.address() == address {
+            if let Ok(handle) = device.open() {
+                return Some((device, handle));
+            }
+        }
+    }
+
+    None
+}
+
+/// Enumerate attached devices that expose a USBTMC interface.
+pub fn list_devices(context: &mut Context) -> Result<Vec<UsbtmcDeviceInfo>> {
+    let mut found = Vec::new();
+
+    for device in context.devices()?.iter() {
+        let mode = match get_usbtmc_mode(&device) {
+            Ok(mode) => mode,
+            Err(_) => continue,
+        };
+
+        let descriptor = match device.device_descriptor() {
+            Ok(descriptor) => descriptor,
+            Err(_) => continue,
+        };
+        let serial_number = device
+            .open()
+            .ok()
+            .and_then(|handle| handle.read_serial_number_string_ascii(&descriptor).ok());
+
+        found.push(UsbtmcDeviceInfo {
+            vendor_id: descriptor.vendor_id(),
+            product_id: descriptor.product_id(),
+            bus_number: device.bus_number(),
+            address: device.address(),
+            protocol: mode.protocol,
+            serial_number,
+        });
+    }
+
+    Ok(found)
+}
+
+/// Walk the device's configuration descriptors looking for the USBTMC
+/// interface (class 0xFE, subclass 0x03), recording which
+/// configuration/interface/alt-setting to use.
+pub fn get_usbtmc_mode(device: &Device<Context>) -> Result<DeviceMode> {
+    let descriptor = device.device_descriptor()?;
+
+    for cfg_idx in 0..descriptor.num_configurations() {
+        let config = device.config_descriptor(cfg_idx)?;
+
+        for interface in config.interfaces() {
+            for setting in interface.descriptors() {
+                if setting.class_code() == USBTMC_INTERFACE_CLASS
+                    && setting.sub_class_code() == USBTMC_INTERFACE_SUBCLASS
+                {
+                    return Ok(DeviceMode {
+                        config_number: config.number(),
+                        interface_number: interface.number(),
+                        setting_number: setting.setting_number(),
+                        protocol: setting.protocol_code(),
+                        has_kernel_driver: false,
+                    });
+                }
+            }
+        }
+    }
+
+    Err(Error::DeviceNotFound.into())
+}
+
+/// Detach the kernel driver from the USBTMC interface, if one is attached,
+/// recording that it needs to be reattached once the client is dropped.
+pub fn detach_kernel_driver(
+    mode: &mut DeviceMode,
+    handle: &mut DeviceHandle<Context>,
+) -> Result<()> {
+    if handle.kernel_driver_active(mode.interface_number)? {
+        handle.detach_kernel_driver(mode.interface_number)?;
+        mode.has_kernel_driver = true;
+    }
+
+    Ok(())
+}
+
+/// Find the bulk-in/bulk-out endpoint addresses for the USBTMC interface.
+pub fn get_endpoints(mode: &DeviceMode, device: &Device<Context>) -> Result<UsbtmcEndpoints> {
+    let config = device.active_config_descriptor()?;
+
+    let interface = config
+        .interfaces()
+        .find(|interface| interface.number() == mode.interface_number)
+        .ok_or(Error::DeviceNotFound)?;
+
+    let setting = interface
+        .descriptors()
+        .find(|setting| setting.setting_number() == mode.setting_number)
+        .ok_or(Error::DeviceNotFound)?;
+
+    let mut bulk_in_ep = None;
+    let mut bulk_out_ep = None;
+    let mut interrupt_in_ep = None;
+
+    for endpoint in setting.endpoint_descriptors() {
+        match (endpoint.transfer_type(), endpoint.direction()) {
+            (TransferType::Bulk, Direction::In) => bulk_in_ep = Some(endpoint.address()),
+            (TransferType::Bulk, Direction::Out) => bulk_out_ep = Some(endpoint.address()),
+            (TransferType::Interrupt, Direction::In) => interrupt_in_ep = Some(endpoint.address()),
+            _ => {}
+        }
+    }
+
+    Ok(UsbtmcEndpoints {
+        bulk_in_ep: bulk_in_ep.ok_or(Error::DeviceNotFound)?,
+        bulk_out_ep: bulk_out_ep.ok_or(Error::DeviceNotFound)?,
+        interrupt_in_ep,
+    })
+}
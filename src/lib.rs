@@ -57,12 +57,13 @@ mod types;
 mod communication {
     pub mod bulk;
     pub mod control;
+    pub mod interrupt;
 }
 
 use communication::control;
 use constants::misc::DEFAULT_TIMEOUT_DURATION;
 use error::Error;
-use types::{BTag, Capabilities, DeviceMode, Handle, Timeout, UsbtmcEndpoints};
+use types::{BTag, Capabilities, DeviceMode, Handle, Timeout, UsbtmcDeviceInfo, UsbtmcEndpoints};
 
 use anyhow::Result;
 
@@ -93,17 +94,66 @@ impl UsbtmcClient {
     /// - `pid` -> the product ID
     ///
     pub fn connect(vid: u16, pid: u16) -> Result<UsbtmcClient> {
-        // OPEN THE DEVICE
-        // ==========
+        let mut context = rusb::Context::new()?;
+        let (device, handle) = match init::open_device(&mut context, vid, pid, None) {
+            Some(res) => res,
+            None => return Err(Error::DeviceNotFound.into()),
+        };
 
-        // setup context
+        Self::from_device(device, handle)
+    }
+
+    /// ### Connect By Serial
+    ///
+    /// Connect to a specific USB device by vendor ID, product ID and USB
+    /// serial number, for when multiple identical instruments are attached.
+    ///
+    /// #### Arguments
+    /// - `vid` -> the vendor ID
+    /// - `pid` -> the product ID
+    /// - `serial` -> the USB serial-number string descriptor to match
+    ///
+    pub fn connect_by_serial(vid: u16, pid: u16, serial: &str) -> Result<UsbtmcClient> {
         let mut context = rusb::Context::new()?;
-        // attempt to open the device
-        let (device, mut handle) = match init::open_device(&mut context, vid, pid) {
+        let (device, handle) = match init::open_device(&mut context, vid, pid, Some(serial)) {
             Some(res) => res,
             None => return Err(Error::DeviceNotFound.into()),
         };
 
+        Self::from_device(device, handle)
+    }
+
+    /// ### Connect Info
+    ///
+    /// Connect to a device previously returned by [`UsbtmcClient::list`].
+    ///
+    /// #### Arguments
+    /// - `info` -> the device to connect to
+    ///
+    pub fn connect_info(info: &UsbtmcDeviceInfo) -> Result<UsbtmcClient> {
+        let mut context = rusb::Context::new()?;
+        let (device, handle) =
+            match init::open_device_at(&mut context, info.bus_number, info.address) {
+                Some(res) => res,
+                None => return Err(Error::DeviceNotFound.into()),
+            };
+
+        Self::from_device(device, handle)
+    }
+
+    /// ### List
+    ///
+    /// Enumerate attached USB devices that expose a USBTMC interface
+    /// (interface class 0xFE, subclass 0x03).
+    ///
+    pub fn list() -> Result<Vec<UsbtmcDeviceInfo>> {
+        let mut context = rusb::Context::new()?;
+        init::list_devices(&mut context)
+    }
+
+    /// Finish setting up a client from an already-opened device handle,
+    /// shared by `connect`, `connect_by_serial` and `connect_info`.
+    fn from_device(device: rusb::Device<rusb::Context>, mut handle: rusb::DeviceHandle<rusb::Context>) -> Result<UsbtmcClient> {
         // GET THE DEVICE MODE
         // ==========
 
@@ -252,6 +302,274 @@ impl UsbtmcClient {
 
         Ok(String::from(resp))
     }
+
+    /// ### Read Status Byte
+    ///
+    /// Read the USB488 status byte (equivalent to `*STB?`) over the control
+    /// endpoint, via `READ_STATUS_BYTE`.
+    ///
+    /// Requires the device to advertise USB488 support.
+    ///
+    /// Known limitation: on devices with an interrupt-IN endpoint, this waits
+    /// for the notification carrying our own bTag and discards any other
+    /// notification it sees while waiting. A genuine unsolicited Service
+    /// Request that happens to arrive during that wait is dropped rather than
+    /// queued for a concurrent or subsequent [`wait_for_srq`](Self::wait_for_srq) call.
+    ///
+    pub fn read_status_byte(&self) -> Result<u8> {
+        use communication::interrupt;
+
+        self.require_usb488()?;
+
+        let source = control::read_status_byte(
+            &self.handle,
+            self.mode.interface_number,
+            &self.btag,
+            self.endpoints.interrupt_in_ep.is_some(),
+            &self.timeout,
+        )?;
+
+        match source {
+            control::StatusByteSource::Immediate(status_byte) => Ok(status_byte),
+            control::StatusByteSource::Interrupt { btag } => {
+                let endpoint = self
+                    .endpoints
+                    .interrupt_in_ep
+                    .ok_or(Error::NoInterruptEndpoint)?;
+
+                loop {
+                    match interrupt::read_notification(&self.handle, endpoint, *self.timeout.borrow())? {
+                        interrupt::Notification::StatusByte { btag: got, status_byte }
+                            if got == btag =>
+                        {
+                            return Ok(status_byte)
+                        }
+                        _ => continue,
+                    }
+                }
+            }
+        }
+    }
+
+    /// ### Wait For SRQ
+    ///
+    /// Block until the device raises an unsolicited Service Request on its
+    /// interrupt-IN endpoint, returning the status byte it reports.
+    ///
+    /// Requires the device to expose an interrupt-IN endpoint.
+    ///
+    pub fn wait_for_srq(&self, timeout: std::time::Duration) -> Result<u8> {
+        use communication::interrupt;
+
+        let endpoint = self
+            .endpoints
+            .interrupt_in_ep
+            .ok_or(Error::NoInterruptEndpoint)?;
+
+        interrupt::wait_for_srq(&self.handle, endpoint, timeout)
+    }
+
+    /// ### Set Remote Enable
+    ///
+    /// Enable or disable remote control of the device via `REN_CONTROL`.
+    ///
+    /// Requires the device to advertise USB488 support for remote/local control.
+    ///
+    pub fn set_remote_enable(&self, enable: bool) -> Result<()> {
+        self.require_remote_local()?;
+
+        control::ren_control(
+            &self.handle,
+            self.mode.interface_number,
+            enable,
+            &self.timeout,
+        )
+    }
+
+    /// ### Go To Local
+    ///
+    /// Return the device to local control via `GO_TO_LOCAL`.
+    ///
+    /// Requires the device to advertise USB488 support for remote/local control.
+    ///
+    pub fn goto_local(&self) -> Result<()> {
+        self.require_remote_local()?;
+
+        control::go_to_local(&self.handle, self.mode.interface_number, &self.timeout)
+    }
+
+    /// ### Local Lockout
+    ///
+    /// Disable the device's front-panel `LOCAL` key via `LOCAL_LOCKOUT`.
+    ///
+    /// Requires the device to advertise USB488 support for remote/local control.
+    ///
+    pub fn local_lockout(&self) -> Result<()> {
+        self.require_remote_local()?;
+
+        control::local_lockout(&self.handle, self.mode.interface_number, &self.timeout)
+    }
+
+    /// ### Trigger
+    ///
+    /// Send a USB488 TRIGGER message to the bulk-out endpoint (equivalent to
+    /// `*TRG`).
+    ///
+    /// Requires the device to advertise USB488 support for `TRIGGER`.
+    ///
+    pub fn trigger(&self) -> Result<()> {
+        use communication::bulk;
+
+        self.require_trigger()?;
+
+        bulk::trigger(
+            &self.handle,
+            &self.btag,
+            &self.endpoints.bulk_out_ep,
+            &self.timeout,
+        )
+    }
+
+    /// ### Query Stream
+    ///
+    /// Send a command and stream the response to `sink` as each bulk-in
+    /// chunk arrives, instead of buffering the whole response in memory.
+    /// Useful for large transfers such as an oscilloscope waveform capture.
+    ///
+    /// #### Arguments
+    /// - `cmd` -> the command to send
+    /// - `term_char` -> optional terminator byte to stop on; honored only if
+    ///   the device's capabilities advertise TermChar support
+    /// - `sink` -> where to write each chunk as it arrives
+    ///
+    pub fn query_stream(&self, cmd: &str, term_char: Option<u8>, sink: &mut impl std::io::Write) -> Result<()> {
+        use communication::bulk;
+
+        bulk::write(
+            &self.handle,
+            &self.btag,
+            cmd.into(),
+            &self.endpoints.bulk_out_ep,
+            &self.timeout,
+        )?;
+
+        bulk::read_stream(
+            &self.handle,
+            &self.btag,
+            &self.endpoints.bulk_in_ep,
+            &self.endpoints.bulk_out_ep,
+            &self.capabilities,
+            term_char,
+            sink,
+            &self.timeout,
+        )
+    }
+
+    /// ### Write Vendor
+    ///
+    /// Write a raw VENDOR_SPECIFIC_OUT message to the bulk-out endpoint, for
+    /// instruments that expose a proprietary vendor channel alongside their
+    /// SCPI-style command set.
+    ///
+    /// #### Arguments
+    /// - `data` -> the raw bytes to send
+    ///
+    pub fn write_vendor(&self, data: &[u8]) -> Result<()> {
+        use communication::bulk;
+
+        bulk::write_vendor(
+            &self.handle,
+            &self.btag,
+            data,
+            &self.endpoints.bulk_out_ep,
+            &self.timeout,
+        )
+    }
+
+    /// ### Query Vendor
+    ///
+    /// Write a raw VENDOR_SPECIFIC_OUT message, then read back a
+    /// VENDOR_SPECIFIC_IN response.
+    ///
+    /// #### Arguments
+    /// - `data` -> the raw bytes to send
+    ///
+    pub fn query_vendor(&self, data: &[u8]) -> Result<Vec<u8>> {
+        use communication::bulk;
+
+        bulk::write_vendor(
+            &self.handle,
+            &self.btag,
+            data,
+            &self.endpoints.bulk_out_ep,
+            &self.timeout,
+        )?;
+
+        bulk::read_vendor(
+            &self.handle,
+            &self.btag,
+            &self.endpoints.bulk_in_ep,
+            &self.endpoints.bulk_out_ep,
+            &self.timeout,
+        )
+    }
+
+    /// ### Abort
+    ///
+    /// Run the USBTMC abort sequence on both bulk endpoints, recovering from
+    /// a stalled `command`/`query`/`query_raw` call without needing to
+    /// reconnect. Safe to call even if no transfer is actually stuck.
+    ///
+    pub fn abort(&self) -> Result<()> {
+        use communication::bulk;
+
+        bulk::abort_bulk_out(
+            &self.handle,
+            &self.endpoints.bulk_out_ep,
+            self.btag.current_out(),
+            &self.timeout,
+        )?;
+        bulk::abort_bulk_in(
+            &self.handle,
+            &self.endpoints.bulk_in_ep,
+            self.btag.current_in(),
+            &self.timeout,
+        )?;
+
+        Ok(())
+    }
+
+    /// Return an error unless the device advertised USB488 support in its capabilities.
+    fn require_usb488(&self) -> Result<()> {
+        if !self.capabilities.usb488 {
+            return Err(Error::NotUsb488.into());
+        }
+
+        Ok(())
+    }
+
+    /// Return an error unless the device advertised support for `TRIGGER`.
+    fn require_trigger(&self) -> Result<()> {
+        self.require_usb488()?;
+
+        if !self.capabilities.supports_trigger {
+            return Err(Error::CapabilityNotSupported("TRIGGER").into());
+        }
+
+        Ok(())
+    }
+
+    /// Return an error unless the device advertised support for
+    /// `REN_CONTROL`/`GO_TO_LOCAL`/`LOCAL_LOCKOUT`.
+    fn require_remote_local(&self) -> Result<()> {
+        self.require_usb488()?;
+
+        if !self.capabilities.supports_remote_local {
+            return Err(Error::CapabilityNotSupported("REN_CONTROL/GO_TO_LOCAL/LOCAL_LOCKOUT").into());
+        }
+
+        Ok(())
+    }
 }
 
 impl Drop for UsbtmcClient {
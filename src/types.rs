@@ -0,0 +1,144 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Shared handle to the open USB device.
+pub type Handle = Rc<RefCell<rusb::DeviceHandle<rusb::Context>>>;
+
+/// Shared, mutable transfer timeout.
+pub type Timeout = Rc<RefCell<Duration>>;
+
+/// Generates the bTag values that accompany every USBTMC transfer.
+///
+/// Bulk transfers and USB488 control transfers use separate counters because
+/// the USB488 subclass restricts `READ_STATUS_BYTE` et al. to the 2..=127
+/// range, while bulk DEV_DEP/VENDOR_SPECIFIC transfers may use the full
+/// 1..=255 range. The bulk counter itself is shared between the OUT and IN
+/// directions, as required by the USBTMC spec, but the last tag issued to
+/// each direction is tracked separately so that recovery code (`abort`) can
+/// target the transfer that's actually stuck on that endpoint.
+#[derive(Debug)]
+pub struct BTag {
+    bulk: RefCell<u8>,
+    control: RefCell<u8>,
+    last_out: RefCell<u8>,
+    last_in: RefCell<u8>,
+}
+
+impl BTag {
+    pub fn new() -> Self {
+        BTag {
+            bulk: RefCell::new(0),
+            control: RefCell::new(1),
+            last_out: RefCell::new(0),
+            last_in: RefCell::new(0),
+        }
+    }
+
+    fn advance_bulk(&self) -> u8 {
+        let mut tag = self.bulk.borrow_mut();
+        *tag = if *tag >= 255 { 1 } else { *tag + 1 };
+        *tag
+    }
+
+    /// Next bTag for a pure bulk-OUT transfer (`DEV_DEP_MSG_OUT`,
+    /// `VENDOR_SPECIFIC_OUT`, `TRIGGER`), cycling through 1..=255.
+    pub fn next_out(&self) -> u8 {
+        let tag = self.advance_bulk();
+        *self.last_out.borrow_mut() = tag;
+        tag
+    }
+
+    /// Next bTag for a `REQUEST_DEV_DEP_MSG_IN`/`REQUEST_VENDOR_SPECIFIC_IN`
+    /// round trip, cycling through 1..=255. The same tag is sent on the
+    /// bulk-OUT request and echoed back on the bulk-IN response, so it's
+    /// recorded as the most recently issued tag for both directions.
+    pub fn next_in(&self) -> u8 {
+        let tag = self.advance_bulk();
+        *self.last_out.borrow_mut() = tag;
+        *self.last_in.borrow_mut() = tag;
+        tag
+    }
+
+    /// Next bTag for a USB488 control transfer, cycling through 2..=127.
+    pub fn next_control(&self) -> u8 {
+        let mut tag = self.control.borrow_mut();
+        *tag = if *tag >= 127 { 2 } else { *tag + 1 };
+        *tag
+    }
+
+    /// The most recently issued bTag for a bulk-OUT transfer, without
+    /// advancing the counter.
+    pub fn current_out(&self) -> u8 {
+        *self.last_out.borrow()
+    }
+
+    /// The most recently issued bTag for a bulk-IN transfer, without
+    /// advancing the counter.
+    pub fn current_in(&self) -> u8 {
+        *self.last_in.borrow()
+    }
+}
+
+impl Default for BTag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Capabilities reported by the device in response to `GET_CAPABILITIES`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Capabilities {
+    /// Device advertises the USB488 subclass.
+    pub usb488: bool,
+    /// Device accepts the `TRIGGER` USB488 message.
+    pub supports_trigger: bool,
+    /// Device accepts `REN_CONTROL` / `GO_TO_LOCAL` / `LOCAL_LOCKOUT`.
+    pub supports_remote_local: bool,
+    /// Device supports `INDICATOR_PULSE`.
+    pub supports_indicator_pulse: bool,
+    /// Device supports terminating a bulk-in transfer on a TermChar byte.
+    pub term_char_enabled: bool,
+}
+
+/// The configuration/interface/alt-setting the USBTMC interface was found on.
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceMode {
+    pub config_number: u8,
+    pub interface_number: u8,
+    pub setting_number: u8,
+    /// USBTMC interface protocol byte: 0 for plain USBTMC, 1 for USB488.
+    pub protocol: u8,
+    pub has_kernel_driver: bool,
+}
+
+impl DeviceMode {
+    /// Whether the interface advertises the USB488 protocol.
+    pub fn is_usb488(&self) -> bool {
+        self.protocol == 1
+    }
+}
+
+/// Information about an attached USBTMC device, as returned by
+/// [`UsbtmcClient::list`](crate::UsbtmcClient::list).
+#[derive(Debug, Clone)]
+pub struct UsbtmcDeviceInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub bus_number: u8,
+    pub address: u8,
+    /// USBTMC interface protocol byte: 0 for plain USBTMC, 1 for USB488.
+    pub protocol: u8,
+    /// The device's USB serial-number string descriptor, if it has one.
+    pub serial_number: Option<String>,
+}
+
+/// Endpoint addresses used to talk to the device.
+#[derive(Debug, Clone, Copy)]
+pub struct UsbtmcEndpoints {
+    pub bulk_in_ep: u8,
+    pub bulk_out_ep: u8,
+    /// Interrupt-IN endpoint used for Service Request notifications, if the
+    /// device exposes one.
+    pub interrupt_in_ep: Option<u8>,
+}